@@ -3,60 +3,878 @@
 //! This crate provides the annotations needed to generate binding between Rust
 //! and another language. (Currently, Dart).
 //!
-//! Currently, it provides the attribute macro `#[flusty]` which can be used on
-//! functions, structs, and enums (in the working). The macro will also make
-//! the code in Rust compile in the C ABI, making it possible in the FFI.
+//! Currently, it provides the attribute macro `#[rua]` which can be used on
+//! functions, structs, enums, unions, and impl blocks, and which takes
+//! arguments such as `name = "..."` or `with_free`. The macro will also make
+//! the code in Rust compile in the C ABI, making it possible in the FFI. See
+//! [macro@rua] for what it does to each kind of item.
 #![warn(clippy::all, missing_docs)]
 use proc_macro2::TokenStream;
-use syn::{parse_macro_input, Item, ItemEnum, ItemFn, ItemStruct};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    visit_mut::{self, VisitMut},
+    Expr, ExprLit, FnArg, GenericParam, Generics, Ident, ImplItem, ImplItemFn,
+    Item, ItemEnum, ItemFn, ItemImpl, ItemStruct, ItemUnion, Lit, LitStr, Meta,
+    Pat, Token, Type, Visibility,
+};
 
 extern crate proc_macro;
 
 /// The attribute macro that makes the code in Rust compile in the C ABI.
 /// - If applied to a function, say `fn foo() -> i32`, it will make the
-///   function `#[no_mangle] pub extern "C" fn foo() -> i32`.
-/// - If applied to a struct or enum, it will make the struct or enum
-///   `#[repr(C)]`.
-/// - If applied to anything else, it will panic.
+///   function `#[no_mangle] pub extern "C" fn foo() -> i32`. Any other
+///   attributes on the function, such as `#[inline]`, are kept. A private
+///   function is made `pub`, while a restricted visibility such as
+///   `pub(crate)` is rejected, since it would be ignored.
+/// - If applied to a struct, enum, or union, it will make it `#[repr(C)]`.
+/// - If applied to an `impl` block, say `impl Point { pub fn x(&self) }`, it
+///   will keep the block and export each `pub` method as a free function
+///   `Point_x`. A `&self` or `&mut self` receiver becomes a leading
+///   `this: *mut Point` argument, which makes the function `unsafe`, as does
+///   an `unsafe` method. `Self` in the signature becomes `Point`. The doc
+///   comments and `#[cfg]` attributes of the method are copied over.
+/// - If applied to anything else, it will emit a compile error.
+///
+/// The macro accepts the following arguments:
+/// - `allow_improper_ctypes`: adds `#[allow(improper_ctypes_definitions)]`
+///   to an annotated function, or to every method wrapper of an annotated
+///   impl block, silencing the lint for those functions only.
+/// - `name = "..."`: exports an annotated function under the given symbol
+///   with `#[export_name = "..."]` instead of `#[no_mangle]`.
+/// - `repr = "u8"`: fixes the integer width of an annotated enum. One of
+///   `u8`, `u16`, `u32`, `u64`, `i8`, `i16`, `i32`, or `i64`. A C-like enum
+///   becomes `#[repr(u8)]`, since Rust rejects `#[repr(C, u8)]` on enums
+///   without fields, and an enum with fields becomes `#[repr(C, u8)]`.
+/// - `with_free`: also exports `unsafe extern "C" fn Foo_free(ptr: *mut Foo)`
+///   for an annotated struct `Foo`, which drops a `Box<Foo>` handed out as a
+///   raw pointer. Nothing is generated for structs without fields.
 #[proc_macro_attribute]
 pub fn rua(
-    _attr: proc_macro::TokenStream,
+    attr: proc_macro::TokenStream,
     item: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(attr as FlustyArgs);
     let item = parse_macro_input!(item as Item);
-    handle_item(&item).into()
+    handle_item(&item, &args).into()
 }
 
-fn handle_item(item: &Item) -> TokenStream {
+/// The arguments of the attribute macro, i.e. the `...` in `#[rua(...)]`.
+///
+/// `#[rua]` and `#[rua()]` both parse to the default arguments.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct FlustyArgs {
+    /// Set to the `allow_improper_ctypes` path if `improper_ctypes_definitions`
+    /// should be allowed on the exported functions.
+    allow_improper_ctypes: Option<syn::Path>,
+    /// The symbol to export a function under, if not its own name.
+    name: Option<LitStr>,
+    /// The integer type backing an enum, e.g. `u8`.
+    repr: Option<Ident>,
+    /// Set to the `with_free` path if a struct should get a `_free` function.
+    with_free: Option<syn::Path>,
+}
+
+/// The integer types accepted by the `repr` argument.
+const ENUM_REPRS: [&str; 8] =
+    ["u8", "u16", "u32", "u64", "i8", "i16", "i32", "i64"];
+
+impl Parse for FlustyArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = FlustyArgs::default();
+        let metas = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+        for meta in metas {
+            match meta {
+                Meta::Path(path) if path.is_ident("allow_improper_ctypes") => {
+                    args.allow_improper_ctypes = Some(path);
+                }
+                Meta::Path(path) if path.is_ident("with_free") => {
+                    args.with_free = Some(path);
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("repr") => {
+                    match nv.value {
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(repr),
+                            ..
+                        }) if ENUM_REPRS.contains(&repr.value().as_str()) => {
+                            args.repr =
+                                Some(Ident::new(&repr.value(), repr.span()));
+                        }
+                        value => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                format!(
+                                    "`repr` must be one of {}",
+                                    ENUM_REPRS.join(", ")
+                                ),
+                            ));
+                        }
+                    }
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("name") => {
+                    match nv.value {
+                        Expr::Lit(ExprLit {
+                            lit: Lit::Str(name),
+                            ..
+                        }) => args.name = Some(name),
+                        value => {
+                            return Err(syn::Error::new_spanned(
+                                value,
+                                "`name` must be a string literal",
+                            ));
+                        }
+                    }
+                }
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta.path(),
+                        "unknown flusty argument",
+                    ));
+                }
+            }
+        }
+        Ok(args)
+    }
+}
+
+fn handle_item(item: &Item, args: &FlustyArgs) -> TokenStream {
+    if let Some(name) = &args.name {
+        if !matches!(item, Item::Fn(_)) {
+            return syn::Error::new_spanned(
+                name,
+                "`name` can only be used on functions",
+            )
+            .to_compile_error();
+        }
+    }
+    if let Some(allow) = &args.allow_improper_ctypes {
+        if !matches!(item, Item::Fn(_) | Item::Impl(_)) {
+            return syn::Error::new_spanned(
+                allow,
+                "`allow_improper_ctypes` can only be used on functions and \
+                 impl blocks",
+            )
+            .to_compile_error();
+        }
+    }
+    if let Some(repr) = &args.repr {
+        if !matches!(item, Item::Enum(_)) {
+            return syn::Error::new_spanned(
+                repr,
+                "`repr` can only be used on enums",
+            )
+            .to_compile_error();
+        }
+    }
+    if let Some(with_free) = &args.with_free {
+        if !matches!(item, Item::Struct(_)) {
+            return syn::Error::new_spanned(
+                with_free,
+                "`with_free` can only be used on structs",
+            )
+            .to_compile_error();
+        }
+    }
+    match item {
+        Item::Fn(f) => handle_item_fn(f, args),
+        Item::Struct(s) => handle_item_struct(s, args),
+        Item::Enum(e) => handle_item_enum(e, args),
+        Item::Union(u) => handle_item_union(u),
+        Item::Impl(i) => handle_item_impl(i, args),
+        item => syn::Error::new_spanned(
+            item,
+            format!(
+                "flusty can only be used on functions, structs, enums, \
+                 unions, and impl blocks, not on {}",
+                item_kind(item)
+            ),
+        )
+        .to_compile_error(),
+    }
+}
+
+/// Returns a human readable name for the kind of an item.
+fn item_kind(item: &Item) -> &'static str {
     match item {
-        Item::Fn(f) => handle_item_fn(f),
-        Item::Struct(s) => handle_item_struct(s),
-        Item::Enum(e) => handle_item_enum(e),
-        _ => panic!("flusty can only be used on functions"),
+        Item::Const(_) => "a const item",
+        Item::Enum(_) => "an enum",
+        Item::ExternCrate(_) => "an extern crate",
+        Item::Fn(_) => "a function",
+        Item::ForeignMod(_) => "an extern block",
+        Item::Impl(_) => "an impl block",
+        Item::Macro(_) => "a macro invocation",
+        Item::Mod(_) => "a module",
+        Item::Static(_) => "a static item",
+        Item::Struct(_) => "a struct",
+        Item::Trait(_) => "a trait",
+        Item::TraitAlias(_) => "a trait alias",
+        Item::Type(_) => "a type alias",
+        Item::Union(_) => "a union",
+        Item::Use(_) => "a use declaration",
+        _ => "this item",
+    }
+}
+
+/// Returns a compile error if `generics` has type or const parameters, which
+/// have no C equivalent. Lifetimes are allowed.
+fn reject_generics(generics: &Generics, msg: &str) -> Option<TokenStream> {
+    generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, GenericParam::Lifetime(_)))
+        .map(|_| syn::Error::new_spanned(generics, msg).to_compile_error())
+}
+
+fn handle_item_fn(f: &ItemFn, args: &FlustyArgs) -> TokenStream {
+    if let Some(error) = reject_generics(
+        &f.sig.generics,
+        "`extern \"C\"` functions cannot be generic",
+    ) {
+        return error;
+    }
+    if let Visibility::Restricted(vis) = &f.vis {
+        return syn::Error::new_spanned(
+            vis,
+            "exported functions are always made `pub`, so this restricted \
+             visibility would be ignored; use `pub` or leave the \
+             visibility out",
+        )
+        .to_compile_error();
+    }
+    let mut f = f.clone();
+    f.attrs.retain(|attr| {
+        !attr.path().is_ident("no_mangle")
+            && !attr.path().is_ident("export_name")
+    });
+    if args.allow_improper_ctypes.is_some() {
+        f.attrs
+            .push(syn::parse_quote!(#[allow(improper_ctypes_definitions)]));
+    }
+    f.attrs.push(match &args.name {
+        Some(name) => syn::parse_quote!(#[export_name = #name]),
+        None => syn::parse_quote!(#[no_mangle]),
+    });
+    f.vis = syn::parse_quote!(pub);
+    f.sig.abi = Some(syn::parse_quote!(extern "C"));
+    quote::quote!(#f)
+}
+
+fn handle_item_impl(i: &ItemImpl, args: &FlustyArgs) -> TokenStream {
+    if let Some((_, path, _)) = &i.trait_ {
+        return syn::Error::new_spanned(
+            path,
+            "flusty cannot be used on trait implementations",
+        )
+        .to_compile_error();
+    }
+    if let Some(error) = reject_generics(
+        &i.generics,
+        "impl blocks with exported methods cannot be generic",
+    ) {
+        return error;
+    }
+    let type_name = match i.self_ty.as_ref() {
+        Type::Path(p) if p.qself.is_none() => {
+            p.path.segments.last().map(|s| &s.ident)
+        }
+        _ => None,
+    };
+    let Some(type_name) = type_name else {
+        return syn::Error::new_spanned(
+            &i.self_ty,
+            "flusty can only be used on impl blocks of named types",
+        )
+        .to_compile_error();
+    };
+    let methods = i.items.iter().filter_map(|item| match item {
+        ImplItem::Fn(m) if matches!(m.vis, Visibility::Public(_)) => {
+            Some(handle_impl_item_fn(type_name, i, m, args))
+        }
+        _ => None,
+    });
+    quote::quote! {
+        #i
+        #(#methods)*
+    }
+}
+
+/// Replaces `Self` with the type of the impl block, since the exported free
+/// functions live outside of it.
+struct ReplaceSelf<'a>(&'a Type);
+
+impl VisitMut for ReplaceSelf<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        match ty {
+            Type::Path(p) if p.qself.is_none() && p.path.is_ident("Self") => {
+                *ty = self.0.clone();
+            }
+            _ => visit_mut::visit_type_mut(self, ty),
+        }
     }
 }
 
-fn handle_item_fn(f: &ItemFn) -> TokenStream {
-    let sig = &f.sig;
-    let body = &f.block;
+/// Generates the exported free function for a method, e.g. `Point_distance`
+/// for `Point::distance`.
+fn handle_impl_item_fn(
+    type_name: &syn::Ident,
+    i: &ItemImpl,
+    m: &ImplItemFn,
+    flusty_args: &FlustyArgs,
+) -> TokenStream {
+    let self_ty = i.self_ty.as_ref();
+    if let Some(error) =
+        reject_generics(&m.sig.generics, "exported methods cannot be generic")
+    {
+        return error;
+    }
+    // Only lifetimes are left here, and the wrapper needs to declare the
+    // ones of both the impl block and the method.
+    let mut generics = i.generics.clone();
+    generics
+        .params
+        .extend(m.sig.generics.params.iter().cloned());
+    if let Some(where_clause) = &m.sig.generics.where_clause {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(where_clause.predicates.iter().cloned());
+    }
+    let where_clause = &generics.where_clause;
+    let method = &m.sig.ident;
+    let name = quote::format_ident!("{}_{}", type_name, method);
+    let mut inputs = Vec::new();
+    let mut args = Vec::new();
+    let mut receiver = None;
+    let mut unsafety = m.sig.unsafety.map(|u| quote::quote!(#u));
+    for arg in &m.sig.inputs {
+        match arg {
+            FnArg::Receiver(r) if r.colon_token.is_some() => {
+                return syn::Error::new_spanned(
+                    r,
+                    "flusty does not support typed `self` receivers",
+                )
+                .to_compile_error();
+            }
+            FnArg::Receiver(r) if r.reference.is_some() => {
+                inputs.push(quote::quote!(this: *mut #self_ty));
+                unsafety = Some(quote::quote!(unsafe));
+                receiver = Some(match r.mutability {
+                    Some(_) => quote::quote!((&mut *this)),
+                    None => quote::quote!((&*this)),
+                });
+            }
+            FnArg::Receiver(_) => {
+                inputs.push(quote::quote!(this: #self_ty));
+                receiver = Some(quote::quote!(this));
+            }
+            FnArg::Typed(arg) => match arg.pat.as_ref() {
+                Pat::Ident(pat) => {
+                    let ident = &pat.ident;
+                    let mut ty = arg.ty.as_ref().clone();
+                    ReplaceSelf(self_ty).visit_type_mut(&mut ty);
+                    inputs.push(quote::quote!(#ident: #ty));
+                    args.push(ident);
+                }
+                _ => {
+                    return syn::Error::new_spanned(
+                        arg,
+                        "flusty requires method arguments to be identifiers",
+                    )
+                    .to_compile_error();
+                }
+            },
+        }
+    }
+    let attrs = m.attrs.iter().filter(|attr| {
+        attr.path().is_ident("doc") || attr.path().is_ident("cfg")
+    });
+    let mut output = m.sig.output.clone();
+    ReplaceSelf(self_ty).visit_return_type_mut(&mut output);
+    let allow = flusty_args
+        .allow_improper_ctypes
+        .as_ref()
+        .map(|_| quote::quote!(#[allow(improper_ctypes_definitions)]));
+    let call = match receiver {
+        Some(receiver) => quote::quote!(#receiver.#method(#(#args),*)),
+        None => quote::quote!(<#self_ty>::#method(#(#args),*)),
+    };
     quote::quote! {
+        #(#attrs)*
+        #allow
+        #[allow(non_snake_case)]
         #[no_mangle]
-        pub extern "C" #sig {
-            #body
+        pub #unsafety extern "C" fn #name #generics(#(#inputs),*) #output
+        #where_clause
+        {
+            #call
         }
     }
 }
 
-fn handle_item_struct(s: &ItemStruct) -> TokenStream {
+fn handle_item_struct(s: &ItemStruct, args: &FlustyArgs) -> TokenStream {
+    if let Some(error) = reject_generics(
+        &s.generics,
+        "`#[repr(C)]` structs passed over FFI cannot be generic",
+    ) {
+        return error;
+    }
+    let free = (args.with_free.is_some() && !s.fields.is_empty()).then(|| {
+        let ident = &s.ident;
+        let name = quote::format_ident!("{}_free", ident);
+        quote::quote! {
+            #[allow(non_snake_case)]
+            #[no_mangle]
+            pub unsafe extern "C" fn #name(ptr: *mut #ident) {
+                if !ptr.is_null() {
+                    drop(Box::from_raw(ptr));
+                }
+            }
+        }
+    });
     quote::quote! {
         #[repr(C)]
         #s
+        #free
     }
 }
 
-fn handle_item_enum(e: &ItemEnum) -> TokenStream {
+fn handle_item_enum(e: &ItemEnum, args: &FlustyArgs) -> TokenStream {
+    if let Some(error) = reject_generics(
+        &e.generics,
+        "`#[repr(C)]` enums passed over FFI cannot be generic",
+    ) {
+        return error;
+    }
+    let repr = match &args.repr {
+        Some(int) if e.variants.iter().all(|v| v.fields.is_empty()) => {
+            quote::quote!(#[repr(#int)])
+        }
+        Some(int) => quote::quote!(#[repr(C, #int)]),
+        None => quote::quote!(#[repr(C)]),
+    };
     quote::quote! {
-        #[repr(C)]
+        #repr
         #e
     }
 }
+
+fn handle_item_union(u: &ItemUnion) -> TokenStream {
+    if let Some(error) = reject_generics(
+        &u.generics,
+        "`#[repr(C)]` unions passed over FFI cannot be generic",
+    ) {
+        return error;
+    }
+    quote::quote! {
+        #[repr(C)]
+        #u
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fn_attributes_are_kept() {
+        let item: Item = syn::parse_quote! {
+            #[inline]
+            #[no_mangle]
+            fn foo() -> i32 {
+                1
+            }
+        };
+        let f: ItemFn =
+            syn::parse2(handle_item(&item, &FlustyArgs::default())).unwrap();
+        let attrs = f
+            .attrs
+            .iter()
+            .map(|attr| attr.path().get_ident().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(attrs, ["inline", "no_mangle"]);
+    }
+
+    #[test]
+    fn fn_docs_and_cfgs_are_kept() {
+        let item: Item = syn::parse_quote! {
+            /// Does the extra thing.
+            #[cfg(feature = "extra")]
+            fn extra() {}
+        };
+        let f: ItemFn =
+            syn::parse2(handle_item(&item, &FlustyArgs::default())).unwrap();
+        assert_eq!(f.attrs.len(), 3);
+        assert!(f.attrs[0].path().is_ident("doc"));
+        assert!(f.attrs[1].path().is_ident("cfg"));
+        assert!(f.attrs[2].path().is_ident("no_mangle"));
+    }
+
+    #[test]
+    fn fn_item_survives_intact() {
+        let item: Item = syn::parse_quote! {
+            /// Reads through a pointer.
+            #[must_use]
+            unsafe extern "C" fn read(ptr: *const i32) -> i32 {
+                *ptr
+            }
+        };
+        let f: ItemFn =
+            syn::parse2(handle_item(&item, &FlustyArgs::default())).unwrap();
+        assert!(matches!(f.vis, Visibility::Public(_)));
+        assert!(f.sig.unsafety.is_some());
+        assert!(f.sig.abi.is_some());
+        assert_eq!(f.attrs.len(), 3);
+        assert!(f.attrs[1].path().is_ident("must_use"));
+        let ItemFn { block, .. } = syn::parse_quote! {
+            fn read() {
+                *ptr
+            }
+        };
+        assert_eq!(f.block, block);
+    }
+
+    #[test]
+    fn method_docs_and_cfgs_are_kept() {
+        let item: Item = syn::parse_quote! {
+            impl Point {
+                /// The x coordinate.
+                #[cfg(feature = "extra")]
+                #[inline]
+                pub fn x(&self) -> f64 {
+                    self.x
+                }
+            }
+        };
+        let output = handle_item(&item, &FlustyArgs::default());
+        let file: syn::File = syn::parse2(output).unwrap();
+        let Item::Fn(f) = &file.items[1] else {
+            panic!("expected a function");
+        };
+        let attrs = f
+            .attrs
+            .iter()
+            .map(|attr| attr.path().get_ident().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(attrs, ["doc", "cfg", "allow", "no_mangle"]);
+    }
+
+    #[test]
+    fn empty_args_are_default() {
+        let bare: FlustyArgs = syn::parse2(quote::quote!()).unwrap();
+        assert_eq!(bare, FlustyArgs::default());
+    }
+
+    #[test]
+    fn allow_improper_ctypes_only_when_requested() {
+        let item: Item = syn::parse_quote! {
+            fn wide(x: u128) -> u128 {
+                x
+            }
+        };
+        let allow = quote::quote!(#[allow(improper_ctypes_definitions)]);
+        let args = FlustyArgs::default();
+        let output = handle_item(&item, &args).to_string();
+        assert!(!output.contains(&allow.to_string()));
+        let args: FlustyArgs =
+            syn::parse2(quote::quote!(allow_improper_ctypes)).unwrap();
+        let output = handle_item(&item, &args).to_string();
+        assert!(output.contains(&allow.to_string()));
+        let item: Item = syn::parse_quote! {
+            impl Wide {
+                pub fn get(&self) -> u128 {
+                    self.0
+                }
+            }
+        };
+        let output = handle_item(&item, &args).to_string();
+        assert!(output.contains(&allow.to_string()));
+        let item: Item = syn::parse_quote!(
+            struct Wide(u128);
+        );
+        let output = handle_item(&item, &args).to_string();
+        assert!(output.contains("can only be used on functions"));
+    }
+
+    #[test]
+    fn impl_methods_are_exported() {
+        let item: Item = syn::parse_quote! {
+            impl Point {
+                pub fn new(x: f64, y: f64) -> Point {
+                    Point { x, y }
+                }
+
+                pub fn distance(&self, other: Point) -> f64 {
+                    (self.x - other.x).hypot(self.y - other.y)
+                }
+
+                fn private(&mut self) {}
+            }
+        };
+        let output = handle_item(&item, &FlustyArgs::default());
+        let file: syn::File = syn::parse2(output).unwrap();
+        assert!(matches!(file.items[0], Item::Impl(_)));
+        let fns = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(f) => Some(f),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(fns.len(), 2);
+        assert_eq!(fns[0].sig.ident, "Point_new");
+        assert!(fns[0].sig.unsafety.is_none());
+        assert_eq!(fns[1].sig.ident, "Point_distance");
+        assert!(fns[1].sig.unsafety.is_some());
+        let this = &fns[1].sig.inputs[0];
+        assert_eq!(
+            quote::quote!(#this).to_string(),
+            quote::quote!(this: *mut Point).to_string()
+        );
+    }
+
+    #[test]
+    fn impl_method_self_types_and_unsafety() {
+        let item: Item = syn::parse_quote! {
+            impl Point {
+                pub fn new(x: f64, y: f64) -> Self {
+                    Self { x, y }
+                }
+
+                pub fn add(self, other: Self) -> Option<Self> {
+                    None
+                }
+
+                pub unsafe fn raw(x: i32) -> i32 {
+                    x
+                }
+            }
+        };
+        let output = handle_item(&item, &FlustyArgs::default());
+        let file: syn::File = syn::parse2(output).unwrap();
+        let sigs = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(f) => Some(&f.sig),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let new = sigs[0];
+        assert_eq!(
+            quote::quote!(#new).to_string(),
+            quote::quote!(extern "C" fn Point_new(x: f64, y: f64) -> Point)
+                .to_string()
+        );
+        let add = sigs[1];
+        assert_eq!(
+            quote::quote!(#add).to_string(),
+            quote::quote!(
+                extern "C" fn Point_add(this: Point, other: Point)
+                    -> Option<Point>
+            )
+            .to_string()
+        );
+        assert!(sigs[2].unsafety.is_some());
+    }
+
+    #[test]
+    fn unsupported_items_are_compile_errors() {
+        let item: Item = syn::parse_quote! {
+            trait Shape {}
+        };
+        let output = handle_item(&item, &FlustyArgs::default()).to_string();
+        assert!(output.starts_with(":: core :: compile_error !"));
+        assert!(output.contains("not on a trait"));
+    }
+
+    #[test]
+    fn name_sets_the_export_name() {
+        let item: Item = syn::parse_quote! {
+            fn new_point() {}
+        };
+        let args: FlustyArgs =
+            syn::parse2(quote::quote!(name = "point_create")).unwrap();
+        let f: ItemFn = syn::parse2(handle_item(&item, &args)).unwrap();
+        assert_eq!(f.attrs.len(), 1);
+        assert!(f.attrs[0].path().is_ident("export_name"));
+        let Meta::NameValue(nv) = &f.attrs[0].meta else {
+            panic!("expected `export_name = ...`");
+        };
+        let value = &nv.value;
+        assert_eq!(
+            quote::quote!(#value).to_string(),
+            quote::quote!("point_create").to_string()
+        );
+        assert!(syn::parse2::<FlustyArgs>(quote::quote!(name = 1)).is_err());
+    }
+
+    #[test]
+    fn unions_become_repr_c() {
+        let item: Item = syn::parse_quote! {
+            /// The bits of a float.
+            pub union FloatBits {
+                f: f32,
+                bits: u32,
+            }
+        };
+        let output = handle_item(&item, &FlustyArgs::default());
+        let Item::Union(mut u) = syn::parse2(output).unwrap() else {
+            panic!("expected a union");
+        };
+        let repr = u.attrs.remove(0);
+        assert_eq!(
+            quote::quote!(#repr).to_string(),
+            quote::quote!(#[repr(C)]).to_string()
+        );
+        assert_eq!(Item::Union(u), item);
+    }
+
+    #[test]
+    fn struct_repr_align_is_kept() {
+        let item: Item = syn::parse_quote! {
+            #[repr(C, align(16))]
+            struct Aligned {
+                x: u8,
+            }
+        };
+        let output = handle_item(&item, &FlustyArgs::default());
+        let s: ItemStruct = syn::parse2(output).unwrap();
+        let repr = &s.attrs[1];
+        assert_eq!(
+            quote::quote!(#repr).to_string(),
+            quote::quote!(#[repr(C, align(16))]).to_string()
+        );
+    }
+
+    #[test]
+    fn enum_repr_sets_the_width() {
+        let args: FlustyArgs = syn::parse2(quote::quote!(repr = "u8")).unwrap();
+        let items: [(Item, TokenStream); 2] = [
+            (
+                syn::parse_quote!(
+                    enum Color {
+                        Red,
+                        Green,
+                    }
+                ),
+                quote::quote!(#[repr(u8)]),
+            ),
+            (
+                syn::parse_quote!(
+                    enum Shape {
+                        Circle(f32),
+                        Square(f32),
+                    }
+                ),
+                quote::quote!(#[repr(C, u8)]),
+            ),
+        ];
+        for (item, expected) in items {
+            let e: ItemEnum = syn::parse2(handle_item(&item, &args)).unwrap();
+            let repr = &e.attrs[0];
+            assert_eq!(quote::quote!(#repr).to_string(), expected.to_string());
+        }
+        assert!(
+            syn::parse2::<FlustyArgs>(quote::quote!(repr = "usize")).is_err()
+        );
+    }
+
+    #[test]
+    fn with_free_exports_a_destructor() {
+        let args: FlustyArgs = syn::parse2(quote::quote!(with_free)).unwrap();
+        let item: Item = syn::parse_quote! {
+            struct Foo {
+                x: i32,
+            }
+        };
+        let file: syn::File = syn::parse2(handle_item(&item, &args)).unwrap();
+        let Item::Fn(free) = &file.items[1] else {
+            panic!("expected a function");
+        };
+        assert_eq!(free.sig.ident, "Foo_free");
+        assert!(free.sig.unsafety.is_some());
+        let item: Item = syn::parse_quote!(
+            struct Marker;
+        );
+        let file: syn::File = syn::parse2(handle_item(&item, &args)).unwrap();
+        assert_eq!(file.items.len(), 1);
+    }
+
+    #[test]
+    fn restricted_visibility_is_rejected() {
+        let item: Item = syn::parse_quote! {
+            pub(in crate::ffi) fn hidden() {}
+        };
+        let output = handle_item(&item, &FlustyArgs::default());
+        assert!(output.to_string().contains("would be ignored"));
+    }
+
+    #[test]
+    fn generics_are_rejected() {
+        let items: [Item; 5] = [
+            syn::parse_quote!(
+                fn id<T>(x: T) -> T {
+                    x
+                }
+            ),
+            syn::parse_quote!(
+                impl<T: Copy> W<T> {
+                    pub fn get(&self) -> i32 {
+                        0
+                    }
+                }
+            ),
+            syn::parse_quote!(
+                impl W {
+                    pub fn map<F: Fn(i32) -> i32>(&self, f: F) -> i32 {
+                        f(0)
+                    }
+                }
+            ),
+            syn::parse_quote!(
+                struct Buf<const N: usize>([u8; N]);
+            ),
+            syn::parse_quote!(
+                enum Either<L, R> {
+                    Left(L),
+                    Right(R),
+                }
+            ),
+        ];
+        for item in items {
+            let output = handle_item(&item, &FlustyArgs::default());
+            assert!(output.to_string().contains("cannot be generic"));
+        }
+        let item: Item = syn::parse_quote! {
+            fn first<'a>(xs: &'a [u8]) -> &'a u8 {
+                &xs[0]
+            }
+        };
+        let output = handle_item(&item, &FlustyArgs::default());
+        assert!(syn::parse2::<ItemFn>(output).is_ok());
+        let item: Item = syn::parse_quote! {
+            impl<'a> View<'a> {
+                pub fn first<'b>(&self, xs: &'b [u8]) -> &'b u8 {
+                    &xs[0]
+                }
+            }
+        };
+        let output = handle_item(&item, &FlustyArgs::default());
+        let file: syn::File = syn::parse2(output).unwrap();
+        let Item::Fn(f) = &file.items[1] else {
+            panic!("expected a function");
+        };
+        let generics = &f.sig.generics;
+        assert_eq!(
+            quote::quote!(#generics).to_string(),
+            quote::quote!(<'a, 'b>).to_string()
+        );
+    }
+
+    #[test]
+    fn unknown_args_are_rejected() {
+        assert!(syn::parse2::<FlustyArgs>(quote::quote!(foo)).is_err());
+    }
+}
@@ -3,15 +3,18 @@
 #![deny(clippy::all)]
 
 use std::{
+    cell::Cell,
     error::Error,
     fmt::{Debug, Display},
 };
 
 use proc_macro2::Span;
+use quote::ToTokens;
 use syn::{
-    spanned::Spanned, Expr, Field, FnArg, ItemEnum, ItemFn, ItemStruct, Pat,
-    ReturnType, Type, TypeArray, TypePath, TypePtr, TypeSlice, TypeTuple,
-    Variant,
+    parenthesized, spanned::Spanned, token, Attribute, Expr, ExprLit, Field,
+    FnArg, GenericArgument, ItemEnum, ItemFn, ItemStruct, Lit, LitInt, Pat,
+    PathArguments, PathSegment, ReturnType, Token, Type, TypeArray, TypeBareFn,
+    TypePath, TypePtr, TypeReference, TypeSlice, TypeTuple, Variant,
 };
 
 /// Represents something that can be described.
@@ -28,11 +31,12 @@ impl Descriptable for &ItemStruct {
 
 impl Descriptable for &Field {
     fn description(&self) -> String {
-        format!(
-            "field {}: {}",
-            self.ident.as_ref().unwrap(),
-            (&self.ty).description()
-        )
+        match &self.ident {
+            Some(ident) => {
+                format!("field {}: {}", ident, (&self.ty).description())
+            }
+            None => format!("field {}", (&self.ty).description()),
+        }
     }
 }
 
@@ -44,7 +48,11 @@ impl Descriptable for &FnArg {
                 Pat::Ident(ident) => {
                     format!("{}: {}", ident.ident, (&*pat.ty).description())
                 }
-                _ => panic!("Unsupported pattern: {:?}", pat),
+                other => format!(
+                    "{}: {}",
+                    other.to_token_stream(),
+                    (&*pat.ty).description()
+                ),
             },
         }
     }
@@ -105,7 +113,7 @@ impl Descriptable for &ReturnType {
 
 impl Descriptable for Expr {
     fn description(&self) -> String {
-        todo!()
+        self.to_token_stream().to_string()
     }
 }
 
@@ -121,7 +129,43 @@ impl Descriptable for &TypeArray {
 
 impl Descriptable for &Type {
     fn description(&self) -> String {
-        todo!()
+        self.to_token_stream().to_string()
+    }
+}
+
+impl Descriptable for &TypePtr {
+    fn description(&self) -> String {
+        self.to_token_stream().to_string()
+    }
+}
+
+impl Descriptable for &TypeReference {
+    fn description(&self) -> String {
+        self.to_token_stream().to_string()
+    }
+}
+
+impl Descriptable for &TypeSlice {
+    fn description(&self) -> String {
+        self.to_token_stream().to_string()
+    }
+}
+
+impl Descriptable for &TypeBareFn {
+    fn description(&self) -> String {
+        self.to_token_stream().to_string()
+    }
+}
+
+impl Descriptable for &TypePath {
+    fn description(&self) -> String {
+        self.to_token_stream().to_string()
+    }
+}
+
+impl Descriptable for &TypeTuple {
+    fn description(&self) -> String {
+        self.to_token_stream().to_string()
     }
 }
 
@@ -190,6 +234,7 @@ pub struct ConversionError {
 }
 
 /// A builder for `ConversionError`.
+#[derive(Default)]
 pub struct ConversionErrorBuilder {
     /// The error.
     error: ConversionError,
@@ -398,6 +443,143 @@ impl RsModule {
             funcs,
         }
     }
+
+    /// Returns the number of functions in this module and its submodules.
+    pub fn total_functions(&self) -> usize {
+        self.funcs.len()
+            + self
+                .submodules
+                .iter()
+                .map(|m| m.total_functions())
+                .sum::<usize>()
+    }
+
+    /// Returns the number of structs in this module and its submodules.
+    pub fn total_structs(&self) -> usize {
+        self.structs.len()
+            + self
+                .submodules
+                .iter()
+                .map(|m| m.total_structs())
+                .sum::<usize>()
+    }
+
+    /// Returns the number of enums in this module and its submodules.
+    pub fn total_enums(&self) -> usize {
+        self.enums.len()
+            + self
+                .submodules
+                .iter()
+                .map(|m| m.total_enums())
+                .sum::<usize>()
+    }
+
+    /// Returns the C symbol of every function in this module and its
+    /// submodules: the `#[rua(name = "...")]` of the function if it has one,
+    /// and its name otherwise. Methods of impl blocks are not parsed, so their
+    /// `Type_method` wrappers are not listed.
+    pub fn exported_symbols(&self) -> Vec<String> {
+        let mut symbols = self
+            .funcs
+            .iter()
+            .map(|f| f.symbol.clone().unwrap_or_else(|| f.name.clone()))
+            .collect::<Vec<_>>();
+        for module in &self.submodules {
+            symbols.extend(module.exported_symbols());
+        }
+        symbols
+    }
+
+    /// Checks that everything in this module and its submodules can cross the
+    /// C ABI, and returns every problem found.
+    pub fn validate_ffi(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        self.validate_ffi_in(self, &mut diagnostics);
+        diagnostics
+    }
+
+    fn validate_ffi_in(
+        &self,
+        root: &RsModule,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        for s in &self.structs {
+            for field in s.fields.iter().filter(|f| !f.ty.is_ffi_safe(root)) {
+                diagnostics.push(Diagnostic::new(
+                    format!("struct {}", s.name),
+                    format!(
+                        "field `{}` of {} is not FFI-safe",
+                        field.name, field.ty
+                    ),
+                ));
+            }
+        }
+        for e in &self.enums {
+            for variant in e.variants.iter().filter(|v| !v.fields.is_empty()) {
+                diagnostics.push(Diagnostic::new(
+                    format!("enum {}", e.name),
+                    format!("variant `{}` carries data", variant.name),
+                ));
+            }
+        }
+        for f in &self.funcs {
+            for arg in f.args.iter().filter(|a| !a.ty.is_ffi_safe(root)) {
+                diagnostics.push(Diagnostic::new(
+                    format!("fn {}", f.name),
+                    format!(
+                        "argument `{}` of {} is not FFI-safe",
+                        arg.name, arg.ty
+                    ),
+                ));
+            }
+            if let Some(ret) = f.ret.as_ref().filter(|r| !r.is_ffi_safe(root)) {
+                diagnostics.push(Diagnostic::new(
+                    format!("fn {}", f.name),
+                    format!("return {} is not FFI-safe", ret),
+                ));
+            }
+        }
+        for module in &self.submodules {
+            module.validate_ffi_in(root, diagnostics);
+        }
+    }
+
+    /// Finds a struct by name in this module or any of its submodules.
+    pub fn find_struct(&self, name: &str) -> Option<&RsStruct> {
+        self.structs.iter().find(|s| s.name == name).or_else(|| {
+            self.submodules.iter().find_map(|m| m.find_struct(name))
+        })
+    }
+
+    /// Finds an enum by name in this module or any of its submodules.
+    pub fn find_enum(&self, name: &str) -> Option<&RsEnum> {
+        self.enums
+            .iter()
+            .find(|e| e.name == name)
+            .or_else(|| self.submodules.iter().find_map(|m| m.find_enum(name)))
+    }
+}
+
+/// A problem found while validating a module, see [RsModule::validate_ffi].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    /// The item the problem was found in, e.g. `fn foo`.
+    pub item: String,
+    /// The message.
+    pub msg: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.item, self.msg)
+    }
+}
+
+impl Diagnostic {
+    /// Creates a new diagnostic.
+    pub fn new(item: String, msg: String) -> Self {
+        Self { item, msg }
+    }
 }
 
 /// Represents a type in Rust.
@@ -416,10 +598,16 @@ pub enum RsType {
     Array(RsArray),
     /// Wraps around a [RsSlice].
     Slice(RsSlice),
+    /// Wraps around a [RsVec].
+    Vec(RsVec),
     /// Wraps around a [RsFn].
     Func(RsFn),
     /// Wraps around a [RsPointer].
     Pointer(RsPointer),
+    /// A struct or enum referred to by name, e.g. `Point` in `*mut Point`.
+    /// Only the name is known where the type is used, so it is resolved with
+    /// [RsModule::find_struct] or [RsModule::find_enum].
+    Named(String),
     /// Wraps a unit type.
     Unit,
 }
@@ -433,18 +621,394 @@ impl Display for RsType {
             RsType::Tuple(ref v) => write!(f, "type {}", v),
             RsType::Array(ref v) => write!(f, "type {}", v),
             RsType::Slice(ref v) => write!(f, "type {}", v),
+            RsType::Vec(ref v) => write!(f, "type {}", v),
             RsType::Func(ref v) => write!(f, "type {}", v),
             RsType::Pointer(ref v) => write!(f, "type {}", v),
+            RsType::Named(ref v) => write!(f, "type {}", v),
             RsType::Unit => write!(f, "type ()"),
         }
     }
 }
 
+impl RsType {
+    /// Returns whether the type can cross the C ABI as is.
+    ///
+    /// Structs, enums and [RsType::Named] references are only considered
+    /// safe if they are declared in `module` (and are therefore
+    /// `#[repr(C)]`); enums must additionally be C-like. Pointers are safe
+    /// unless they point to an unsized type, which would make them fat
+    /// pointers.
+    pub fn is_ffi_safe(&self, module: &RsModule) -> bool {
+        match self {
+            RsType::Struct(s) => {
+                module.find_struct(&s.name).is_some()
+                    && s.fields.iter().all(|f| f.ty.is_ffi_safe(module))
+            }
+            RsType::Enum(e) => {
+                module.find_enum(&e.name).is_some()
+                    && e.variants.iter().all(|v| v.fields.is_empty())
+            }
+            // `char` has no C equivalent, rustc warns about it as well.
+            RsType::Primitive(p) => !matches!(
+                p,
+                RsPrimitive::I128
+                    | RsPrimitive::U128
+                    | RsPrimitive::Char
+                    | RsPrimitive::Str
+                    | RsPrimitive::String
+                    | RsPrimitive::Void
+            ),
+            RsType::Tuple(_) => false,
+            RsType::Array(a) => a.ty.is_ffi_safe(module),
+            RsType::Slice(_) => false,
+            // A `Vec` has to be split into a pointer and a length first.
+            RsType::Vec(_) => false,
+            RsType::Func(f) => {
+                f.args.iter().all(|a| a.ty.is_ffi_safe(module))
+                    && f.ret.as_ref().is_none_or(|r| r.is_ffi_safe(module))
+            }
+            RsType::Pointer(p) => !matches!(
+                p.ty.as_ref(),
+                RsType::Slice(_) | RsType::Primitive(RsPrimitive::Str)
+            ),
+            RsType::Named(name) => {
+                if let Some(s) = module.find_struct(name) {
+                    s.fields.iter().all(|f| f.ty.is_ffi_safe(module))
+                } else if let Some(e) = module.find_enum(name) {
+                    e.variants.iter().all(|v| v.fields.is_empty())
+                } else {
+                    false
+                }
+            }
+            RsType::Unit => true,
+        }
+    }
+
+    /// Returns the size of the type in bytes under the C ABI, or `None` if
+    /// the type has no C layout. [RsType::Named] references are resolved
+    /// in `module`, and have no layout if they are not declared there.
+    pub fn size_hint(
+        &self,
+        module: &RsModule,
+        width: PointerWidth,
+    ) -> Option<usize> {
+        self.layout_hint(module, width).map(|(size, _)| size)
+    }
+
+    /// Returns the alignment of the type in bytes under the C ABI, or `None`
+    /// if the type has no C layout. See [RsType::size_hint].
+    pub fn align_hint(
+        &self,
+        module: &RsModule,
+        width: PointerWidth,
+    ) -> Option<usize> {
+        self.layout_hint(module, width).map(|(_, align)| align)
+    }
+
+    /// Returns the size and alignment of the type.
+    fn layout_hint(
+        &self,
+        module: &RsModule,
+        width: PointerWidth,
+    ) -> Option<(usize, usize)> {
+        let ptr = width.bytes();
+        match self {
+            RsType::Struct(s) => s.layout_hint(module, width),
+            RsType::Enum(e) => e.layout_hint(width),
+            RsType::Primitive(p) => p.layout_hint(width),
+            RsType::Tuple(_) => None,
+            RsType::Array(a) => {
+                a.ty.layout_hint(module, width)
+                    .map(|(size, align)| (size * a.len, align))
+            }
+            RsType::Slice(_) => None,
+            RsType::Vec(_) => None,
+            RsType::Func(_) => Some((ptr, ptr)),
+            RsType::Pointer(p) => match p.ty.as_ref() {
+                RsType::Slice(_) | RsType::Primitive(RsPrimitive::Str) => {
+                    Some((2 * ptr, ptr))
+                }
+                _ => Some((ptr, ptr)),
+            },
+            RsType::Named(name) => {
+                if let Some(s) = module.find_struct(name) {
+                    s.layout_hint(module, width)
+                } else {
+                    module.find_enum(name)?.layout_hint(width)
+                }
+            }
+            RsType::Unit => Some((0, 1)),
+        }
+    }
+}
+
+/// The width of a pointer on the target platform, used when computing type
+/// layouts.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerWidth {
+    /// 32-bit pointers.
+    Bits32,
+    /// 64-bit pointers.
+    #[default]
+    Bits64,
+}
+
+impl PointerWidth {
+    /// Returns the size of a pointer in bytes.
+    pub fn bytes(self) -> usize {
+        match self {
+            PointerWidth::Bits32 => 4,
+            PointerWidth::Bits64 => 8,
+        }
+    }
+}
+
+/// The default for [set_type_depth_limit].
+pub const DEFAULT_TYPE_DEPTH_LIMIT: usize = 64;
+
+thread_local! {
+    static TYPE_DEPTH_LIMIT: Cell<usize> =
+        const { Cell::new(DEFAULT_TYPE_DEPTH_LIMIT) };
+    static TYPE_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Sets how deeply types may be nested before converting them fails, for
+/// conversions on the current thread.
+pub fn set_type_depth_limit(limit: usize) {
+    TYPE_DEPTH_LIMIT.set(limit);
+}
+
+/// Counts one level of type nesting for as long as it is alive.
+struct TypeDepthGuard;
+
+impl TypeDepthGuard {
+    fn enter() -> Self {
+        TYPE_DEPTH.set(TYPE_DEPTH.get() + 1);
+        Self
+    }
+}
+
+impl Drop for TypeDepthGuard {
+    fn drop(&mut self) {
+        TYPE_DEPTH.set(TYPE_DEPTH.get() - 1);
+    }
+}
+
 impl TryFrom<&Type> for RsType {
     type Error = ConversionError;
 
     fn try_from(value: &Type) -> Result<Self, Self::Error> {
-        todo!()
+        let limit = TYPE_DEPTH_LIMIT.get();
+        if TYPE_DEPTH.get() >= limit {
+            return Err(ConversionErrorBuilder::new()
+                .with_source("Type")
+                .with_destination("RsType")
+                .with_data(&value)
+                .with_message(format!(
+                    "type is nested more than {} levels deep",
+                    limit
+                ))
+                .with_span((&value.span()).into())
+                .build());
+        }
+        let _guard = TypeDepthGuard::enter();
+        match value {
+            Type::Macro(mac) => {
+                let name = mac
+                    .mac
+                    .path
+                    .segments
+                    .iter()
+                    .map(|s| s.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::");
+                Err(ConversionErrorBuilder::new()
+                    .with_source("Type::Macro")
+                    .with_destination("RsType")
+                    .with_data(&value)
+                    .with_message(format!(
+                        "cannot expand type macro `{}!`, write the concrete \
+                         type instead",
+                        name
+                    ))
+                    .with_span((&value.span()).into())
+                    .build())
+            }
+            Type::Path(path) => RsType::try_from(path),
+            Type::Ptr(ptr) => RsPointer::try_from(ptr).map(RsType::from),
+            Type::Reference(reference) => {
+                RsPointer::try_from(reference).map(RsType::from)
+            }
+            Type::Slice(slice) => RsSlice::try_from(slice).map(RsType::from),
+            Type::BareFn(func) => RsFn::try_from(func).map(RsType::from),
+            // `()` is parsed as an empty tuple, but is represented as
+            // [RsType::Unit], the same as a missing return type.
+            Type::Tuple(tuple) if tuple.elems.is_empty() => Ok(RsType::Unit),
+            Type::Tuple(tuple) => RsTuple::try_from(tuple).map(RsType::from),
+            Type::Array(array) => RsArray::try_from(array).map(RsType::from),
+            Type::Group(group) => RsType::try_from(group.elem.as_ref()),
+            Type::Paren(paren) => RsType::try_from(paren.elem.as_ref()),
+            _ => {
+                let variant = match value {
+                    Type::ImplTrait(_) => "Type::ImplTrait",
+                    Type::Infer(_) => "Type::Infer",
+                    Type::Never(_) => "Type::Never",
+                    Type::TraitObject(_) => "Type::TraitObject",
+                    Type::Verbatim(_) => "Type::Verbatim",
+                    _ => "Type",
+                };
+                Err(ConversionErrorBuilder::new()
+                    .with_source(variant)
+                    .with_destination("RsType")
+                    .with_data(&value)
+                    .with_message(format!("unsupported type `{}`", variant))
+                    .with_span((&value.span()).into())
+                    .build())
+            }
+        }
+    }
+}
+
+/// Returns the only generic type argument of a path segment, e.g. `T` in
+/// `NonNull<T>`.
+fn single_type_argument(segment: &PathSegment) -> Option<&Type> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(GenericArgument::Type(ty)) => Some(ty),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+impl TryFrom<&TypePath> for RsType {
+    type Error = ConversionError;
+
+    fn try_from(value: &TypePath) -> Result<Self, Self::Error> {
+        let last = value.path.segments.last();
+        match last {
+            // `Box<T>` is passed as the raw pointer from `Box::into_raw`.
+            Some(segment)
+                if segment.ident == "NonNull" || segment.ident == "Box" =>
+            {
+                let inner = single_type_argument(segment).ok_or_else(|| {
+                    ConversionErrorBuilder::new()
+                        .with_source("TypePath")
+                        .with_destination("RsPointer")
+                        .with_data(&value)
+                        .with_message(format!(
+                            "{} must have one type argument",
+                            segment.ident
+                        ))
+                        .with_span((&value.span()).into())
+                        .build()
+                })?;
+                let ty = RsType::try_from(inner).map_err(|e| {
+                    ConversionErrorBuilder::new()
+                        .with_source_opt(&e.src)
+                        .with_destination("RsPointer")
+                        .with_data(&value)
+                        .with_error_source(e)
+                        .with_span((&value.span()).into())
+                        .build()
+                })?;
+                Ok(RsPointer::new(ty, true).into())
+            }
+            Some(segment) if segment.ident == "Vec" => {
+                let inner = single_type_argument(segment).ok_or_else(|| {
+                    ConversionErrorBuilder::new()
+                        .with_source("TypePath")
+                        .with_destination("RsVec")
+                        .with_data(&value)
+                        .with_message("Vec must have one type argument")
+                        .with_span((&value.span()).into())
+                        .build()
+                })?;
+                let ty = RsType::try_from(inner).map_err(|e| {
+                    ConversionErrorBuilder::new()
+                        .with_source_opt(&e.src)
+                        .with_destination("RsVec")
+                        .with_data(&value)
+                        .with_error_source(e)
+                        .with_span((&value.span()).into())
+                        .build()
+                })?;
+                Ok(RsVec::new(ty).into())
+            }
+            // An `Option` of a non-null pointer is a pointer that may be
+            // null. Other options have no null value to use across the C ABI.
+            Some(segment) if segment.ident == "Option" => {
+                let inner = single_type_argument(segment);
+                if let Some(Type::Ptr(_)) = inner {
+                    return Err(ConversionErrorBuilder::new()
+                        .with_source("TypePath")
+                        .with_destination("RsPointer")
+                        .with_data(&value)
+                        .with_message(
+                            "a raw pointer is already nullable and has no \
+                             niche for `Option`, pass the pointer bare",
+                        )
+                        .with_span((&value.span()).into())
+                        .build());
+                }
+                let inner = inner
+                    .filter(|inner| match inner {
+                        Type::Path(path) => {
+                            path.path.segments.last().is_some_and(|s| {
+                                s.ident == "NonNull" || s.ident == "Box"
+                            })
+                        }
+                        _ => false,
+                    })
+                    .ok_or_else(|| {
+                        ConversionErrorBuilder::new()
+                            .with_source("TypePath")
+                            .with_destination("RsPointer")
+                            .with_data(&value)
+                            .with_message(
+                                "only `Option<NonNull<T>>` and \
+                                 `Option<Box<T>>` can be null across FFI, \
+                                 pass a raw pointer instead",
+                            )
+                            .with_span((&value.span()).into())
+                            .build()
+                    })?;
+                let RsType::Pointer(pointer) = RsType::try_from(inner)? else {
+                    unreachable!("`Option` only wraps pointer types here");
+                };
+                Ok(RsPointer {
+                    nullable: true,
+                    ..pointer
+                }
+                .into())
+            }
+            Some(segment) if segment.ident == "c_void" => {
+                Ok(RsPrimitive::Void.into())
+            }
+            _ => match (path_primitive(value), last) {
+                (Some(primitive), _) => Ok(primitive.into()),
+                // Any other name refers to a user type, which is looked up
+                // by its last segment, so `crate::geo::Point` is `Point`.
+                (None, Some(segment))
+                    if value.qself.is_none()
+                        && segment.arguments.is_empty() =>
+                {
+                    Ok(RsType::Named(segment.ident.to_string()))
+                }
+                (None, _) => Err(ConversionErrorBuilder::new()
+                    .with_source("TypePath")
+                    .with_destination("RsType")
+                    .with_data(&value)
+                    .with_message(
+                        "generic and qualified `<T as Trait>` types are not \
+                         supported",
+                    )
+                    .with_span((&value.span()).into())
+                    .build()),
+            },
+        }
     }
 }
 
@@ -455,6 +1019,11 @@ pub struct RsStruct {
     pub name: String,
     /// The fields of the struct.
     pub fields: Vec<RsField>,
+    /// The alignment requested with `#[repr(align(n))]`, if any.
+    pub align: Option<usize>,
+    /// The largest field alignment allowed by `#[repr(packed(n))]`, or `1`
+    /// for `#[repr(packed)]`, if the struct is packed.
+    pub packed: Option<usize>,
 }
 
 impl Display for RsStruct {
@@ -472,8 +1041,93 @@ impl Display for RsStruct {
 impl RsStruct {
     /// Creates a new struct.
     pub fn new(name: String, fields: Vec<RsField>) -> Self {
-        Self { name, fields }
+        Self {
+            name,
+            fields,
+            align: None,
+            packed: None,
+        }
     }
+
+    /// Returns the size of the struct in bytes under the C ABI, or `None` if
+    /// one of its fields has no C layout. Fields of named struct and enum
+    /// types are resolved in `module`.
+    pub fn size_hint(
+        &self,
+        module: &RsModule,
+        width: PointerWidth,
+    ) -> Option<usize> {
+        self.layout_hint(module, width).map(|(size, _)| size)
+    }
+
+    /// Returns the alignment of the struct in bytes under the C ABI, or
+    /// `None` if one of its fields has no C layout.
+    pub fn align_hint(
+        &self,
+        module: &RsModule,
+        width: PointerWidth,
+    ) -> Option<usize> {
+        self.layout_hint(module, width).map(|(_, align)| align)
+    }
+
+    /// Lays the fields out in order, padding each to its alignment (capped by
+    /// the packing), then pads the whole struct to the largest alignment.
+    fn layout_hint(
+        &self,
+        module: &RsModule,
+        width: PointerWidth,
+    ) -> Option<(usize, usize)> {
+        let mut size = 0usize;
+        let mut align = self.align.unwrap_or(1);
+        for field in &self.fields {
+            let (field_size, field_align) =
+                field.ty.layout_hint(module, width)?;
+            let field_align = self
+                .packed
+                .map_or(field_align, |packed| field_align.min(packed));
+            size = size.next_multiple_of(field_align) + field_size;
+            align = align.max(field_align);
+        }
+        Some((size.next_multiple_of(align), align))
+    }
+}
+
+/// Names a tuple field after its position, e.g. `field_0`, so that it can be
+/// converted like a named field.
+fn positional(i: usize, field: &Field) -> Field {
+    let mut field = field.clone();
+    if field.ident.is_none() {
+        field.ident = Some(quote::format_ident!("field_{}", i));
+    }
+    field
+}
+
+/// Reads `n` from a `#[repr(align(n))]` (or `#[repr(C, align(n))]`)
+/// attribute, and the packing from `#[repr(packed)]` or `#[repr(packed(n))]`.
+fn repr_layout(
+    attrs: &[Attribute],
+) -> syn::Result<(Option<usize>, Option<usize>)> {
+    let mut align = None;
+    let mut packed = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.input.peek(token::Paren) {
+                let content;
+                parenthesized!(content in meta.input);
+                if meta.path.is_ident("align") {
+                    align = Some(content.parse::<LitInt>()?.base10_parse()?);
+                } else if meta.path.is_ident("packed") {
+                    packed = Some(content.parse::<LitInt>()?.base10_parse()?);
+                } else {
+                    content.parse::<proc_macro2::TokenStream>()?;
+                }
+            } else if meta.path.is_ident("packed") {
+                packed = Some(1);
+            }
+            Ok(())
+        })?;
+    }
+    Ok((align, packed))
 }
 
 impl From<RsStruct> for RsType {
@@ -487,21 +1141,35 @@ impl TryFrom<&ItemStruct> for RsStruct {
 
     fn try_from(value: &ItemStruct) -> Result<Self, Self::Error> {
         let name = value.ident.to_string();
-        let fields = value
-            .fields
-            .iter()
-            .map(RsField::try_from)
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| {
-                ConversionErrorBuilder::new()
-                    .with_source_opt(&e.src)
-                    .with_destination("RsStruct")
-                    .with_data(&value)
-                    .with_error_source(e)
-                    .with_span((&value.span()).into())
-                    .build()
-            })?;
-        Ok(Self::new(name, fields))
+        // A unit struct has no fields.
+        let mut fields = Vec::with_capacity(value.fields.len());
+        for (i, field) in value.fields.iter().enumerate() {
+            let field =
+                RsField::try_from(&positional(i, field)).map_err(|e| {
+                    ConversionErrorBuilder::new()
+                        .with_source_opt(&e.src)
+                        .with_destination("RsStruct")
+                        .with_data(&value)
+                        .with_error_source(e)
+                        .with_span((&value.span()).into())
+                        .build()
+                })?;
+            fields.push(field);
+        }
+        let (align, packed) = repr_layout(&value.attrs).map_err(|e| {
+            ConversionErrorBuilder::new()
+                .with_source("ItemStruct")
+                .with_destination("RsStruct")
+                .with_data(&value)
+                .with_message(format!("Invalid repr attribute: {}", e))
+                .with_span((&e.span()).into())
+                .build()
+        })?;
+        Ok(Self {
+            align,
+            packed,
+            ..Self::new(name, fields)
+        })
     }
 }
 
@@ -512,6 +1180,8 @@ pub struct RsField {
     pub name: String,
     /// The type of the field.
     pub ty: RsType,
+    /// The doc comment of the field, if any.
+    pub doc: Option<String>,
 }
 
 impl Display for RsField {
@@ -523,10 +1193,31 @@ impl Display for RsField {
 impl RsField {
     /// Creates a new field.
     pub fn new(name: String, ty: RsType) -> Self {
-        Self { name, ty }
+        Self {
+            name,
+            ty,
+            doc: None,
+        }
     }
 }
 
+/// Joins the `#[doc = "..."]` attributes, i.e. the `///` lines, into one
+/// string, dropping the space that usually follows `///`.
+fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let lines = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta.require_name_value().ok()?.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(doc), ..
+            }) => Some(doc.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(String::from).unwrap_or(line))
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
 impl TryFrom<&Field> for RsField {
     type Error = ConversionError;
 
@@ -550,7 +1241,10 @@ impl TryFrom<&Field> for RsField {
                 .with_span((&value.span()).into())
                 .build()
         })?;
-        Ok(Self::new(name, ty))
+        Ok(Self {
+            doc: doc_comment(&value.attrs),
+            ..Self::new(name, ty)
+        })
     }
 }
 
@@ -606,6 +1300,9 @@ pub struct RsEnum {
     pub name: String,
     /// The variants of the enum.
     pub variants: Vec<RsVariant>,
+    /// The integer type set with `#[repr(u8)]` or `#[rua(repr = "u8")]`, if
+    /// any.
+    pub repr: Option<RsPrimitive>,
 }
 
 impl Display for RsEnum {
@@ -623,8 +1320,91 @@ impl Display for RsEnum {
 impl RsEnum {
     /// Creates a new enum.
     pub fn new(name: String, variants: Vec<RsVariant>) -> Self {
-        Self { name, variants }
+        Self {
+            name,
+            variants,
+            repr: None,
+        }
+    }
+
+    /// Returns the size and alignment of a C-like enum, which is that of its
+    /// `repr` integer, or of a C `int` for a plain `#[repr(C)]`. Enums with
+    /// fields have no layout hint.
+    fn layout_hint(&self, width: PointerWidth) -> Option<(usize, usize)> {
+        if !self.variants.iter().all(|v| v.fields.is_empty()) {
+            return None;
+        }
+        match &self.repr {
+            Some(int) => int.layout_hint(width),
+            None => Some((4, 4)),
+        }
+    }
+}
+
+/// Returns the integer primitive named `name`, e.g. [RsPrimitive::U8] for
+/// `u8`.
+fn int_primitive(name: &str) -> Option<RsPrimitive> {
+    match name {
+        "i8" => Some(RsPrimitive::I8),
+        "i16" => Some(RsPrimitive::I16),
+        "i32" => Some(RsPrimitive::I32),
+        "i64" => Some(RsPrimitive::I64),
+        "isize" => Some(RsPrimitive::Isize),
+        "u8" => Some(RsPrimitive::U8),
+        "u16" => Some(RsPrimitive::U16),
+        "u32" => Some(RsPrimitive::U32),
+        "u64" => Some(RsPrimitive::U64),
+        "usize" => Some(RsPrimitive::Usize),
+        _ => None,
+    }
+}
+
+/// Reads the integer type of an enum from `#[repr(u8)]` (or
+/// `#[repr(C, u8)]`), or from the `#[rua(repr = "u8")]` that generates it.
+fn enum_repr(attrs: &[Attribute]) -> syn::Result<Option<RsPrimitive>> {
+    let mut repr = None;
+    for attr in attrs {
+        if attr.path().is_ident("repr") {
+            attr.parse_nested_meta(|meta| {
+                let int = meta.path.get_ident().map(|i| i.to_string());
+                if let Some(int) = int.as_deref().and_then(int_primitive) {
+                    repr = Some(int);
+                }
+                if meta.input.peek(token::Paren) {
+                    let content;
+                    parenthesized!(content in meta.input);
+                    content.parse::<proc_macro2::TokenStream>()?;
+                }
+                Ok(())
+            })?;
+        } else if let Some(int) = rua_str(attr, "repr")? {
+            repr = int_primitive(&int);
+        }
+    }
+    Ok(repr)
+}
+
+/// Reads the string value of `key` from a `#[rua(key = "...")]` attribute.
+fn rua_str(attr: &Attribute, key: &str) -> syn::Result<Option<String>> {
+    let mut value = None;
+    if attr.path().is_ident("rua") && matches!(attr.meta, syn::Meta::List(_)) {
+        attr.parse_nested_meta(|meta| {
+            if !meta.input.peek(Token![=]) {
+                return Ok(());
+            }
+            let expr = meta.value()?.parse::<Expr>()?;
+            if let Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) = expr
+            {
+                if meta.path.is_ident(key) {
+                    value = Some(lit.value());
+                }
+            }
+            Ok(())
+        })?;
     }
+    Ok(value)
 }
 
 impl From<RsEnum> for RsType {
@@ -652,7 +1432,19 @@ impl TryFrom<&ItemEnum> for RsEnum {
                     .with_span((&value.span()).into())
                     .build()
             })?;
-        Ok(Self::new(name, variants))
+        let repr = enum_repr(&value.attrs).map_err(|e| {
+            ConversionErrorBuilder::new()
+                .with_source("ItemEnum")
+                .with_destination("RsEnum")
+                .with_data(&value)
+                .with_message(format!("Invalid repr attribute: {}", e))
+                .with_span((&e.span()).into())
+                .build()
+        })?;
+        Ok(Self {
+            repr,
+            ..Self::new(name, variants)
+        })
     }
 }
 
@@ -689,20 +1481,20 @@ impl TryFrom<&Variant> for RsVariant {
 
     fn try_from(value: &Variant) -> Result<Self, Self::Error> {
         let name = value.ident.to_string();
-        let fields = value
-            .fields
-            .iter()
-            .map(RsField::try_from)
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| {
-                ConversionErrorBuilder::new()
-                    .with_source_opt(&e.src)
-                    .with_destination("RsVariant")
-                    .with_data(&value)
-                    .with_error_source(e)
-                    .with_span((&value.span()).into())
-                    .build()
-            })?;
+        let mut fields = Vec::with_capacity(value.fields.len());
+        for (i, field) in value.fields.iter().enumerate() {
+            let field =
+                RsField::try_from(&positional(i, field)).map_err(|e| {
+                    ConversionErrorBuilder::new()
+                        .with_source_opt(&e.src)
+                        .with_destination("RsVariant")
+                        .with_data(&value)
+                        .with_error_source(e)
+                        .with_span((&value.span()).into())
+                        .build()
+                })?;
+            fields.push(field);
+        }
         Ok(Self::new(name, fields))
     }
 }
@@ -716,6 +1508,9 @@ pub struct RsFn {
     pub args: Vec<RsField>,
     /// The return type of the function.
     pub ret: Option<Box<RsType>>,
+    /// The C symbol set with `#[rua(name = "...")]`, if it differs from the
+    /// function name.
+    pub symbol: Option<String>,
 }
 
 impl Display for RsFn {
@@ -741,6 +1536,7 @@ impl RsFn {
             name,
             args,
             ret: Some(Box::new(ret)),
+            symbol: None,
         }
     }
 }
@@ -777,6 +1573,25 @@ impl TryFrom<&ItemFn> for RsFn {
 
     fn try_from(value: &ItemFn) -> Result<Self, Self::Error> {
         let name = value.sig.ident.to_string();
+        // `[T]` is unsized, so C callers have to pass the data and the
+        // length separately.
+        for arg in &value.sig.inputs {
+            if let FnArg::Typed(pat) = arg {
+                if let Type::Slice(slice) = pat.ty.as_ref() {
+                    return Err(ConversionErrorBuilder::new()
+                        .with_source("FnArg")
+                        .with_destination("RsFn")
+                        .with_data(&arg)
+                        .with_message(format!(
+                            "slices cannot be passed by value, take a \
+                             `*const {0}` and a `usize` length instead",
+                            slice.elem.to_token_stream()
+                        ))
+                        .with_span((&arg.span()).into())
+                        .build());
+                }
+            }
+        }
         let args = value
             .sig
             .inputs
@@ -801,7 +1616,73 @@ impl TryFrom<&ItemFn> for RsFn {
                 .with_span((&value.span()).into())
                 .build()
         })?;
-        Ok(Self::new(name, args, ret))
+        let mut symbol = None;
+        for attr in &value.attrs {
+            if let Some(name) = rua_str(attr, "name").map_err(|e| {
+                ConversionErrorBuilder::new()
+                    .with_source("ItemFn")
+                    .with_destination("RsFn")
+                    .with_data(&value)
+                    .with_message(format!("Invalid rua attribute: {}", e))
+                    .with_span((&e.span()).into())
+                    .build()
+            })? {
+                symbol = Some(name);
+            }
+        }
+        Ok(Self {
+            symbol,
+            ..Self::new(name, args, ret)
+        })
+    }
+}
+
+impl TryFrom<&TypeBareFn> for RsFn {
+    type Error = ConversionError;
+
+    /// Converts a function pointer type. The resulting [RsFn] has an empty
+    /// name, and unnamed arguments are named after their position.
+    fn try_from(value: &TypeBareFn) -> Result<Self, Self::Error> {
+        let is_c_abi = value.abi.as_ref().is_some_and(|abi| {
+            abi.name.as_ref().is_none_or(|name| name.value() == "C")
+        });
+        if !is_c_abi {
+            return Err(ConversionErrorBuilder::new()
+                .with_source("TypeBareFn")
+                .with_destination("RsFn")
+                .with_data(&value)
+                .with_message("function pointers must be `extern \"C\"`")
+                .with_span((&value.span()).into())
+                .build());
+        }
+        let mut args = Vec::with_capacity(value.inputs.len());
+        for (i, arg) in value.inputs.iter().enumerate() {
+            let name = arg
+                .name
+                .as_ref()
+                .map(|(ident, _)| ident.to_string())
+                .unwrap_or_else(|| format!("arg_{}", i));
+            let ty = RsType::try_from(&arg.ty).map_err(|e| {
+                ConversionErrorBuilder::new()
+                    .with_source_opt(&e.src)
+                    .with_destination("RsFn")
+                    .with_data(&value)
+                    .with_error_source(e)
+                    .with_span((&value.span()).into())
+                    .build()
+            })?;
+            args.push(RsField::new(name, ty));
+        }
+        let ret = RsType::try_from(&value.output).map_err(|e| {
+            ConversionErrorBuilder::new()
+                .with_source_opt(&e.src)
+                .with_destination("RsFn")
+                .with_data(&value)
+                .with_error_source(e)
+                .with_span((&value.span()).into())
+                .build()
+        })?;
+        Ok(Self::new(String::new(), args, ret))
     }
 }
 
@@ -849,7 +1730,34 @@ impl TryFrom<&TypeArray> for RsArray {
                 .with_span((&value.span()).into())
                 .build()
         })?;
-        let len = value.len.value() as usize;
+        let len = match &value.len {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => int.base10_parse::<usize>().map_err(|_| {
+                ConversionErrorBuilder::new()
+                    .with_source("TypeArray")
+                    .with_destination("RsArray")
+                    .with_data(&value)
+                    .with_message("Invalid array length")
+                    .with_span((&value.span()).into())
+                    .build()
+            })?,
+            // Const generics and `const` items cannot be resolved from the
+            // type alone, so name the expression to make it easy to replace.
+            len => {
+                return Err(ConversionErrorBuilder::new()
+                    .with_source("TypeArray")
+                    .with_destination("RsArray")
+                    .with_data(&value)
+                    .with_message(format!(
+                        "cannot resolve array length `{}`, use an integer \
+                         literal",
+                        len.to_token_stream()
+                    ))
+                    .with_span((&len.span()).into())
+                    .build());
+            }
+        };
         Ok(Self::new(ty, len))
     }
 }
@@ -867,6 +1775,8 @@ pub enum RsPrimitive {
     I64,
     /// Represents the [i128] type in Rust.
     I128,
+    /// Represents the [isize] type in Rust.
+    Isize,
     /// Represents the [u8] type in Rust.
     U8,
     /// Represents the [u16] type in Rust.
@@ -877,6 +1787,8 @@ pub enum RsPrimitive {
     U64,
     /// Represents the [u128] type in Rust.
     U128,
+    /// Represents the [usize] type in Rust.
+    Usize,
     /// Represents the [f32] type in Rust.
     F32,
     /// Represents the [f64] type in Rust.
@@ -891,6 +1803,33 @@ pub enum RsPrimitive {
     String,
     /// Represents the [()] type in Rust.
     Unit,
+    /// Represents the [core::ffi::c_void] type in Rust, which is only
+    /// meaningful behind a pointer.
+    Void,
+}
+
+impl RsPrimitive {
+    /// Returns the size and alignment of the primitive.
+    fn layout_hint(&self, width: PointerWidth) -> Option<(usize, usize)> {
+        let ptr = width.bytes();
+        match self {
+            RsPrimitive::I8 | RsPrimitive::U8 | RsPrimitive::Bool => {
+                Some((1, 1))
+            }
+            RsPrimitive::I16 | RsPrimitive::U16 => Some((2, 2)),
+            RsPrimitive::I32
+            | RsPrimitive::U32
+            | RsPrimitive::F32
+            | RsPrimitive::Char => Some((4, 4)),
+            RsPrimitive::I64 | RsPrimitive::U64 | RsPrimitive::F64 => {
+                Some((8, 8))
+            }
+            RsPrimitive::I128 | RsPrimitive::U128 => Some((16, 16)),
+            RsPrimitive::Isize | RsPrimitive::Usize => Some((ptr, ptr)),
+            RsPrimitive::Unit => Some((0, 1)),
+            RsPrimitive::Str | RsPrimitive::String | RsPrimitive::Void => None,
+        }
+    }
 }
 
 impl Display for RsPrimitive {
@@ -901,11 +1840,13 @@ impl Display for RsPrimitive {
             RsPrimitive::I32 => write!(f, "i32"),
             RsPrimitive::I64 => write!(f, "i64"),
             RsPrimitive::I128 => write!(f, "i128"),
+            RsPrimitive::Isize => write!(f, "isize"),
             RsPrimitive::U8 => write!(f, "u8"),
             RsPrimitive::U16 => write!(f, "u16"),
             RsPrimitive::U32 => write!(f, "u32"),
             RsPrimitive::U64 => write!(f, "u64"),
             RsPrimitive::U128 => write!(f, "u128"),
+            RsPrimitive::Usize => write!(f, "usize"),
             RsPrimitive::F32 => write!(f, "f32"),
             RsPrimitive::F64 => write!(f, "f64"),
             RsPrimitive::Bool => write!(f, "bool"),
@@ -913,6 +1854,7 @@ impl Display for RsPrimitive {
             RsPrimitive::Str => write!(f, "str"),
             RsPrimitive::String => write!(f, "String"),
             RsPrimitive::Unit => write!(f, "()"),
+            RsPrimitive::Void => write!(f, "c_void"),
         }
     }
 }
@@ -923,11 +1865,42 @@ impl From<RsPrimitive> for RsType {
     }
 }
 
+/// Looks up the primitive a type path names, without building an error for
+/// user types. Only the last segment matters, so `std::primitive::i32` is
+/// `i32`.
+fn path_primitive(value: &TypePath) -> Option<RsPrimitive> {
+    let segment = value
+        .path
+        .segments
+        .last()
+        .filter(|segment| segment.arguments.is_empty())?;
+    let ident = segment.ident.to_string();
+    int_primitive(&ident).or(match ident.as_str() {
+        "i128" => Some(RsPrimitive::I128),
+        "u128" => Some(RsPrimitive::U128),
+        "f32" => Some(RsPrimitive::F32),
+        "f64" => Some(RsPrimitive::F64),
+        "bool" => Some(RsPrimitive::Bool),
+        "char" => Some(RsPrimitive::Char),
+        "str" => Some(RsPrimitive::Str),
+        "String" => Some(RsPrimitive::String),
+        _ => None,
+    })
+}
+
 impl TryFrom<&TypePath> for RsPrimitive {
     type Error = ConversionError;
 
     fn try_from(value: &TypePath) -> Result<Self, Self::Error> {
-        todo!()
+        path_primitive(value).ok_or_else(|| {
+            ConversionErrorBuilder::new()
+                .with_source("TypePath")
+                .with_destination("RsPrimitive")
+                .with_data(&value)
+                .with_message("unknown primitive")
+                .with_span((&value.span()).into())
+                .build()
+        })
     }
 }
 
@@ -938,10 +1911,16 @@ pub struct RsPointer {
     pub ty: Box<RsType>,
     /// Whether the pointer is mutable or not.
     pub mutable: bool,
+    /// Whether the pointer may be null, as for `Option<NonNull<T>>` or
+    /// `Option<Box<T>>`.
+    pub nullable: bool,
 }
 
 impl Display for RsPointer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.nullable {
+            write!(f, "nullable ")?;
+        }
         if self.mutable {
             write!(f, "pointer (*mut {})", self.ty)
         } else {
@@ -956,8 +1935,14 @@ impl RsPointer {
         Self {
             ty: Box::new(ty),
             mutable,
+            nullable: false,
         }
     }
+
+    /// Returns whether the pointer is opaque, i.e. points to `c_void`.
+    pub fn is_opaque(&self) -> bool {
+        matches!(self.ty.as_ref(), RsType::Primitive(RsPrimitive::Void))
+    }
 }
 
 impl From<RsPointer> for RsType {
@@ -970,7 +1955,35 @@ impl TryFrom<&TypePtr> for RsPointer {
     type Error = ConversionError;
 
     fn try_from(value: &TypePtr) -> Result<Self, Self::Error> {
-        todo!()
+        let ty = RsType::try_from(value.elem.as_ref()).map_err(|e| {
+            ConversionErrorBuilder::new()
+                .with_source_opt(&e.src)
+                .with_destination("RsPointer")
+                .with_data(&value)
+                .with_error_source(e)
+                .with_span((&value.span()).into())
+                .build()
+        })?;
+        Ok(Self::new(ty, value.mutability.is_some()))
+    }
+}
+
+impl TryFrom<&TypeReference> for RsPointer {
+    type Error = ConversionError;
+
+    /// References cross the C ABI as pointers, so `&T` becomes
+    /// `*const T` and `&mut T` becomes `*mut T`.
+    fn try_from(value: &TypeReference) -> Result<Self, Self::Error> {
+        let ty = RsType::try_from(value.elem.as_ref()).map_err(|e| {
+            ConversionErrorBuilder::new()
+                .with_source_opt(&e.src)
+                .with_destination("RsPointer")
+                .with_data(&value)
+                .with_error_source(e)
+                .with_span((&value.span()).into())
+                .build()
+        })?;
+        Ok(Self::new(ty, value.mutability.is_some()))
     }
 }
 
@@ -1009,8 +2022,23 @@ impl From<RsTuple> for RsType {
 impl TryFrom<&TypeTuple> for RsTuple {
     type Error = ConversionError;
 
+    /// An empty tuple converts to an empty [RsTuple]. The conversion from
+    /// [Type] turns `()` into [RsType::Unit] before getting here.
     fn try_from(value: &TypeTuple) -> Result<Self, Self::Error> {
-        todo!()
+        let mut types = Vec::with_capacity(value.elems.len());
+        for elem in &value.elems {
+            let ty = RsType::try_from(elem).map_err(|e| {
+                ConversionErrorBuilder::new()
+                    .with_source_opt(&e.src)
+                    .with_destination("RsTuple")
+                    .with_data(&value)
+                    .with_error_source(e)
+                    .with_span((&value.span()).into())
+                    .build()
+            })?;
+            types.push(ty);
+        }
+        Ok(Self::new(types))
     }
 }
 
@@ -1044,6 +2072,745 @@ impl TryFrom<&TypeSlice> for RsSlice {
     type Error = ConversionError;
 
     fn try_from(value: &TypeSlice) -> Result<Self, Self::Error> {
-        todo!()
+        let ty = RsType::try_from(value.elem.as_ref()).map_err(|e| {
+            ConversionErrorBuilder::new()
+                .with_source_opt(&e.src)
+                .with_destination("RsSlice")
+                .with_data(&value)
+                .with_error_source(e)
+                .with_span((&value.span()).into())
+                .build()
+        })?;
+        Ok(Self::new(ty))
+    }
+}
+
+/// Represents a `Vec` in Rust. It crosses the C ABI as a pointer to its
+/// elements and a `usize` length.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RsVec {
+    /// The type of the elements.
+    pub ty: Box<RsType>,
+}
+
+impl Display for RsVec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Vec<{}>", self.ty)
+    }
+}
+
+impl RsVec {
+    /// Creates a new vec.
+    pub fn new(ty: RsType) -> Self {
+        Self { ty: Box::new(ty) }
+    }
+}
+
+impl From<RsVec> for RsType {
+    fn from(v: RsVec) -> Self {
+        Self::Vec(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i32_ty() -> RsType {
+        RsPrimitive::I32.into()
+    }
+
+    fn point() -> RsStruct {
+        RsStruct::new(
+            "Point".to_string(),
+            vec![
+                RsField::new("x".to_string(), i32_ty()),
+                RsField::new("y".to_string(), i32_ty()),
+            ],
+        )
+    }
+
+    fn named() -> RsStruct {
+        RsStruct::new(
+            "Named".to_string(),
+            vec![RsField::new("name".to_string(), RsPrimitive::String.into())],
+        )
+    }
+
+    #[test]
+    fn c_void_pointers_are_opaque() {
+        let ty: Type = syn::parse_quote!(*mut c_void);
+        let RsType::Pointer(ptr) = RsType::try_from(&ty).unwrap() else {
+            panic!("expected a pointer");
+        };
+        assert!(ptr.mutable);
+        assert!(ptr.is_opaque());
+        let ty: Type = syn::parse_quote!(*const std::ffi::c_void);
+        let RsType::Pointer(ptr) = RsType::try_from(&ty).unwrap() else {
+            panic!("expected a pointer");
+        };
+        assert!(!ptr.mutable);
+        assert!(ptr.is_opaque());
+    }
+
+    #[test]
+    fn slice_references_keep_mutability() {
+        let u8_slice = RsType::from(RsSlice::new(RsPrimitive::U8.into()));
+        let ty: Type = syn::parse_quote!(&[u8]);
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsPointer::new(u8_slice.clone(), false).into()
+        );
+        let ty: Type = syn::parse_quote!(&mut [u8]);
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsPointer::new(u8_slice, true).into()
+        );
+    }
+
+    #[test]
+    fn function_pointer_fields() {
+        let item: ItemStruct = syn::parse_quote! {
+            struct VTable {
+                cb: extern "C" fn(i32),
+            }
+        };
+        let callback = RsFn::new(
+            String::new(),
+            vec![RsField::new("arg_0".to_string(), i32_ty())],
+            RsType::Unit,
+        );
+        assert_eq!(
+            RsStruct::try_from(&item).unwrap().fields,
+            [RsField::new("cb".to_string(), callback.into())]
+        );
+        let item: ItemStruct = syn::parse_quote! {
+            struct VTable {
+                cb: fn(i32),
+            }
+        };
+        assert!(RsStruct::try_from(&item).is_err());
+    }
+
+    #[test]
+    fn struct_repr_align() {
+        let item: ItemStruct = syn::parse_quote! {
+            #[repr(C, align(16))]
+            struct Aligned {
+                x: u8,
+            }
+        };
+        assert_eq!(RsStruct::try_from(&item).unwrap().align, Some(16));
+        let item: ItemStruct = syn::parse_quote! {
+            #[repr(C)]
+            #[repr(packed(2))]
+            struct Packed {
+                x: u8,
+                y: u32,
+            }
+        };
+        let packed = RsStruct::try_from(&item).unwrap();
+        assert_eq!((packed.align, packed.packed), (None, Some(2)));
+        let module = RsModule::default();
+        assert_eq!(packed.size_hint(&module, PointerWidth::Bits64), Some(6));
+        assert_eq!(packed.align_hint(&module, PointerWidth::Bits64), Some(2));
+        let item: ItemStruct = syn::parse_quote! {
+            #[repr(C, packed)]
+            struct P {
+                a: u8,
+                b: u32,
+            }
+        };
+        let packed = RsStruct::try_from(&item).unwrap();
+        assert_eq!(packed.size_hint(&module, PointerWidth::Bits64), Some(5));
+        assert_eq!(packed.align_hint(&module, PointerWidth::Bits64), Some(1));
+    }
+
+    #[test]
+    fn struct_layout_hints() {
+        let small = RsStruct::new(
+            "Small".to_string(),
+            vec![
+                RsField::new("a".to_string(), RsPrimitive::U8.into()),
+                RsField::new("b".to_string(), RsPrimitive::U32.into()),
+            ],
+        );
+        let module = RsModule::default();
+        assert_eq!(small.size_hint(&module, PointerWidth::Bits64), Some(8));
+        assert_eq!(small.align_hint(&module, PointerWidth::Bits64), Some(4));
+        let with_ptr = RsStruct::new(
+            "WithPtr".to_string(),
+            vec![
+                RsField::new("a".to_string(), RsPrimitive::U8.into()),
+                RsField::new(
+                    "b".to_string(),
+                    RsPointer::new(RsPrimitive::U8.into(), true).into(),
+                ),
+            ],
+        );
+        assert_eq!(with_ptr.size_hint(&module, PointerWidth::Bits64), Some(16));
+        assert_eq!(with_ptr.align_hint(&module, PointerWidth::Bits64), Some(8));
+        assert_eq!(with_ptr.size_hint(&module, PointerWidth::Bits32), Some(8));
+        assert_eq!(with_ptr.align_hint(&module, PointerWidth::Bits32), Some(4));
+        let outer = RsStruct {
+            align: Some(16),
+            ..RsStruct::new(
+                "Outer".to_string(),
+                vec![RsField::new("inner".to_string(), small.into())],
+            )
+        };
+        assert_eq!(outer.size_hint(&module, PointerWidth::Bits64), Some(16));
+        assert_eq!(outer.align_hint(&module, PointerWidth::Bits64), Some(16));
+        assert_eq!(named().size_hint(&module, PointerWidth::Bits64), None);
+    }
+
+    #[test]
+    fn parsed_layout_hints() {
+        let module = RsModule::new(
+            "crate".to_string(),
+            RsModuleType::CrateModule,
+            vec![],
+            vec![
+                RsStruct::try_from(&syn::parse_quote! {
+                    struct Inner {
+                        a: u64,
+                    }
+                })
+                .unwrap()
+                .into(),
+                RsStruct::try_from(&syn::parse_quote! {
+                    struct Outer {
+                        a: u8,
+                        inner: Inner,
+                    }
+                })
+                .unwrap()
+                .into(),
+                RsEnum::try_from(&syn::parse_quote! {
+                    #[rua(repr = "u8")]
+                    enum Small {
+                        A,
+                        B,
+                    }
+                })
+                .unwrap()
+                .into(),
+                RsEnum::try_from(&syn::parse_quote! {
+                    #[repr(u16)]
+                    enum Medium {
+                        A,
+                    }
+                })
+                .unwrap()
+                .into(),
+                RsStruct::try_from(&syn::parse_quote! {
+                    struct Tagged {
+                        small: Small,
+                        medium: Medium,
+                        missing: Missing,
+                    }
+                })
+                .unwrap()
+                .into(),
+            ],
+        );
+        let layout = |name: &str| {
+            let ty = RsType::Named(name.to_string());
+            (
+                ty.size_hint(&module, PointerWidth::Bits64),
+                ty.align_hint(&module, PointerWidth::Bits64),
+            )
+        };
+        assert_eq!(layout("Outer"), (Some(16), Some(8)));
+        assert_eq!(layout("Small"), (Some(1), Some(1)));
+        assert_eq!(layout("Medium"), (Some(2), Some(2)));
+        assert_eq!(layout("Missing"), (None, None));
+        assert_eq!(layout("Tagged"), (None, None));
+    }
+
+    #[test]
+    fn module_totals() {
+        let color = RsEnum::new("Color".to_string(), vec![]);
+        let func = RsFn::new("area".to_string(), vec![], i32_ty());
+        let child = RsModule::new(
+            "shapes".to_string(),
+            RsModuleType::SubModule {
+                parent: "crate".to_string(),
+            },
+            vec![],
+            vec![point().into(), color.into(), func.clone().into()],
+        );
+        let module = RsModule::new(
+            "crate".to_string(),
+            RsModuleType::CrateModule,
+            vec![child],
+            vec![named().into(), func.into()],
+        );
+        assert_eq!(module.total_functions(), 2);
+        assert_eq!(module.total_structs(), 2);
+        assert_eq!(module.total_enums(), 1);
+    }
+
+    #[test]
+    fn module_exported_symbols() {
+        let child = RsModule::new(
+            "shapes".to_string(),
+            RsModuleType::SubModule {
+                parent: "crate".to_string(),
+            },
+            vec![],
+            vec![RsFn::new("area".to_string(), vec![], i32_ty()).into()],
+        );
+        let renamed: ItemFn = syn::parse_quote! {
+            #[rua(name = "shape_count")]
+            pub fn count() -> i32 { 0 }
+        };
+        let renamed = RsFn::try_from(&renamed).unwrap();
+        assert_eq!(renamed.symbol.as_deref(), Some("shape_count"));
+        let module = RsModule::new(
+            "crate".to_string(),
+            RsModuleType::CrateModule,
+            vec![child],
+            vec![
+                RsFn::new("init".to_string(), vec![], RsType::Unit).into(),
+                renamed.into(),
+            ],
+        );
+        assert_eq!(module.exported_symbols(), ["init", "shape_count", "area"]);
+    }
+
+    #[test]
+    fn module_validate_ffi() {
+        let file: syn::File = syn::parse_quote! {
+            struct Point {
+                x: i32,
+                y: i32,
+            }
+
+            struct Named {
+                name: String,
+            }
+
+            enum Color {
+                Red,
+                Green,
+            }
+
+            fn paint(c: Color, at: Point) {}
+
+            fn pair() -> (i32, i32) {}
+        };
+        let types = file
+            .items
+            .iter()
+            .map(|item| match item {
+                syn::Item::Struct(s) => RsStruct::try_from(s).unwrap().into(),
+                syn::Item::Enum(e) => RsEnum::try_from(e).unwrap().into(),
+                syn::Item::Fn(f) => RsFn::try_from(f).unwrap().into(),
+                _ => unreachable!(),
+            })
+            .collect();
+        let module = RsModule::new(
+            "crate".to_string(),
+            RsModuleType::CrateModule,
+            vec![],
+            types,
+        );
+        let diagnostics = module
+            .validate_ffi()
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            [
+                "struct Named: field `name` of type String is not FFI-safe",
+                "fn pair: return type tuple (type i32, type i32) is not \
+                 FFI-safe",
+            ]
+        );
+    }
+
+    #[test]
+    fn ffi_safe_types() {
+        let color = RsEnum::new(
+            "Color".to_string(),
+            vec![RsVariant::new("Red".to_string(), vec![])],
+        );
+        let module = RsModule::new(
+            "crate".to_string(),
+            RsModuleType::CrateModule,
+            vec![],
+            vec![point().into(), color.clone().into()],
+        );
+        assert!(i32_ty().is_ffi_safe(&module));
+        assert!(RsType::Unit.is_ffi_safe(&module));
+        assert!(RsType::from(point()).is_ffi_safe(&module));
+        assert!(RsType::from(color).is_ffi_safe(&module));
+        assert!(RsType::from(RsArray::new(i32_ty(), 4)).is_ffi_safe(&module));
+        assert!(RsType::from(RsPointer::new(point().into(), true))
+            .is_ffi_safe(&module));
+        let item: ItemFn = syn::parse_quote! {
+            fn paint(c: Color, at: Point) {}
+        };
+        let paint = RsFn::try_from(&item).unwrap();
+        assert!(paint.args.iter().all(|arg| arg.ty.is_ffi_safe(&module)));
+        let ty: Type = syn::parse_quote!(Missing);
+        assert!(!RsType::try_from(&ty).unwrap().is_ffi_safe(&module));
+    }
+
+    #[test]
+    fn ffi_unsafe_types() {
+        let module = RsModule::new(
+            "crate".to_string(),
+            RsModuleType::CrateModule,
+            vec![],
+            vec![named().into()],
+        );
+        assert!(!RsType::from(RsPrimitive::String).is_ffi_safe(&module));
+        assert!(!RsType::from(RsPrimitive::U128).is_ffi_safe(&module));
+        assert!(!RsType::from(RsPrimitive::Char).is_ffi_safe(&module));
+        assert!(!RsType::from(named()).is_ffi_safe(&module));
+        assert!(!RsType::from(point()).is_ffi_safe(&module));
+        assert!(!RsType::from(RsTuple::new(vec![i32_ty(), i32_ty()]))
+            .is_ffi_safe(&module));
+        assert!(!RsType::from(RsPointer::new(
+            RsSlice::new(i32_ty()).into(),
+            false
+        ))
+        .is_ffi_safe(&module));
+        let data = RsEnum::new(
+            "Data".to_string(),
+            vec![RsVariant::new(
+                "Value".to_string(),
+                vec![RsField::new("0".to_string(), i32_ty())],
+            )],
+        );
+        let module = RsModule::new(
+            "crate".to_string(),
+            RsModuleType::CrateModule,
+            vec![],
+            vec![data.clone().into()],
+        );
+        assert!(!RsType::from(data).is_ffi_safe(&module));
+    }
+
+    #[test]
+    fn type_macro_is_an_error() {
+        let item: ItemFn = syn::parse_quote! {
+            fn foo(x: my_type!()) {}
+        };
+        let mut err = RsFn::try_from(&item).unwrap_err();
+        while let Some(source) = err.source {
+            err = *source;
+        }
+        assert_eq!(err.src.as_deref(), Some("Type::Macro"));
+        assert!(err.msg.unwrap().contains("`my_type!`"));
+    }
+
+    #[test]
+    fn non_null_is_a_mutable_pointer() {
+        let ty: Type = syn::parse_quote!(std::ptr::NonNull<()>);
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsPointer::new(RsType::Unit, true).into()
+        );
+        let ty: Type = syn::parse_quote!(NonNull);
+        assert!(RsType::try_from(&ty).is_err());
+    }
+
+    #[test]
+    fn raw_pointers() {
+        let ty: Type = syn::parse_quote!(*const i32);
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsPointer::new(i32_ty(), false).into()
+        );
+        let ty: Type = syn::parse_quote!(*mut Foo);
+        let foo = RsType::Named("Foo".to_string());
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsPointer::new(foo.clone(), true).into()
+        );
+        let ty: Type = syn::parse_quote!(*mut crate::geo::Foo);
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsPointer::new(foo, true).into()
+        );
+        let ty: Type = syn::parse_quote!(*mut Foo<i32>);
+        let err = RsType::try_from(&ty).unwrap_err();
+        assert_eq!(err.dst.as_deref(), Some("RsPointer"));
+        assert!(err.source.is_some());
+    }
+
+    #[test]
+    fn tuples() {
+        let ty: Type = syn::parse_quote!(());
+        assert_eq!(RsType::try_from(&ty).unwrap(), RsType::Unit);
+        let ty: Type = syn::parse_quote!((i32,));
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsTuple::new(vec![i32_ty()]).into()
+        );
+        let ty: Type = syn::parse_quote!((i32, f64, bool));
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsTuple::new(vec![
+                i32_ty(),
+                RsPrimitive::F64.into(),
+                RsPrimitive::Bool.into(),
+            ])
+            .into()
+        );
+    }
+
+    #[test]
+    fn bare_slice_args_are_errors() {
+        let item: ItemFn = syn::parse_quote! {
+            fn sum(xs: [i32]) -> i32 {}
+        };
+        let err = RsFn::try_from(&item).unwrap_err();
+        assert!(err.msg.unwrap().contains("`*const i32` and a `usize`"));
+        let item: ItemFn = syn::parse_quote! {
+            fn sum(xs: &[i32]) -> i32 {}
+        };
+        assert!(RsFn::try_from(&item).is_ok());
+    }
+
+    #[test]
+    fn type_dispatch() {
+        let ty: Type = syn::parse_quote!([i32; 4]);
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsArray::new(i32_ty(), 4).into()
+        );
+        let ty: Type = syn::parse_quote!((i32));
+        assert_eq!(RsType::try_from(&ty).unwrap(), i32_ty());
+        let ty: Type = syn::parse_quote!(impl Copy);
+        let err = RsType::try_from(&ty).unwrap_err();
+        assert_eq!(err.src.as_deref(), Some("Type::ImplTrait"));
+        assert!(err.span.is_some());
+        let ty: Type = syn::parse_quote!(!);
+        let err = RsType::try_from(&ty).unwrap_err();
+        assert_eq!(err.msg.as_deref(), Some("unsupported type `Type::Never`"));
+    }
+
+    #[test]
+    fn option_non_null_is_one_nullable_pointer() {
+        let ty: Type = syn::parse_quote!(Option<NonNull<Foo>>);
+        let RsType::Pointer(pointer) = RsType::try_from(&ty).unwrap() else {
+            panic!("expected a pointer");
+        };
+        assert!(pointer.nullable && pointer.mutable);
+        assert_eq!(*pointer.ty, RsType::Named("Foo".to_string()));
+        let ty: Type = syn::parse_quote!(Option<i32>);
+        assert!(RsType::try_from(&ty).is_err());
+    }
+
+    #[test]
+    fn primitive_paths() {
+        for ty in [
+            syn::parse_quote!(i32),
+            syn::parse_quote!(std::primitive::i32),
+            syn::parse_quote!(::core::primitive::i32),
+        ] {
+            assert_eq!(RsPrimitive::try_from(&ty).unwrap(), RsPrimitive::I32);
+        }
+        for ty in [syn::parse_quote!(i32<u8>), syn::parse_quote!(Foo)] {
+            let err = RsPrimitive::try_from(&ty).unwrap_err();
+            assert_eq!(err.msg.as_deref(), Some("unknown primitive"));
+            assert!(err.span.is_some());
+        }
+    }
+
+    #[test]
+    fn option_pointers_are_nullable() {
+        let nullable = |ty: RsType, mutable| RsPointer {
+            nullable: true,
+            ..RsPointer::new(ty, mutable)
+        };
+        let ty: Type = syn::parse_quote!(Box<i32>);
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            RsPointer::new(i32_ty(), true).into()
+        );
+        let ty: Type = syn::parse_quote!(Option<Box<i32>>);
+        assert_eq!(
+            RsType::try_from(&ty).unwrap(),
+            nullable(i32_ty(), true).into()
+        );
+        let raw: [Type; 2] = [
+            syn::parse_quote!(Option<*mut i32>),
+            syn::parse_quote!(Option<*const i32>),
+        ];
+        for ty in &raw {
+            let err = RsType::try_from(ty).unwrap_err();
+            assert!(err.msg.unwrap().contains("pass the pointer bare"));
+        }
+        let ty: Type = syn::parse_quote!(Option<i32>);
+        let err = RsType::try_from(&ty).unwrap_err();
+        assert!(err.msg.unwrap().contains("pass a raw pointer instead"));
+    }
+
+    #[test]
+    fn vecs() {
+        let ty: Type = syn::parse_quote!(Vec<i32>);
+        let vec = RsType::try_from(&ty).unwrap();
+        assert_eq!(vec, RsVec::new(i32_ty()).into());
+        assert!(!vec.is_ffi_safe(&RsModule::default()));
+        assert_eq!(
+            vec.size_hint(&RsModule::default(), PointerWidth::default()),
+            None
+        );
+        let ty: Type = syn::parse_quote!(Vec<String, A>);
+        assert!(RsType::try_from(&ty).is_err());
+    }
+
+    #[test]
+    fn pointer_sized_integers() {
+        let ty: Type = syn::parse_quote!(usize);
+        let usize_ty = RsType::try_from(&ty).unwrap();
+        assert_eq!(usize_ty, RsPrimitive::Usize.into());
+        assert_eq!(usize_ty.to_string(), "type usize");
+        assert!(usize_ty.is_ffi_safe(&RsModule::default()));
+        assert_eq!(
+            usize_ty.size_hint(&RsModule::default(), PointerWidth::Bits32),
+            Some(4)
+        );
+        assert_eq!(
+            usize_ty.size_hint(&RsModule::default(), PointerWidth::Bits64),
+            Some(8)
+        );
+        let ty: Type = syn::parse_quote!(isize);
+        assert_eq!(RsType::try_from(&ty).unwrap(), RsPrimitive::Isize.into());
+    }
+
+    #[test]
+    fn unresolved_array_lengths() {
+        let ty: TypeArray = syn::parse_quote!([u8; N]);
+        let err = RsArray::try_from(&ty).unwrap_err();
+        assert_eq!(
+            err.msg.as_deref(),
+            Some("cannot resolve array length `N`, use an integer literal")
+        );
+        let ty: TypeArray = syn::parse_quote!([u8; LEN * 2]);
+        let err = RsArray::try_from(&ty).unwrap_err();
+        assert!(err.msg.unwrap().contains("`LEN * 2`"));
+    }
+
+    #[test]
+    fn type_depth_limit() {
+        let nested = |depth| {
+            let mut ty: Type = syn::parse_quote!(i32);
+            for _ in 0..depth {
+                ty = Type::Ptr(TypePtr {
+                    star_token: Default::default(),
+                    const_token: Some(Default::default()),
+                    mutability: None,
+                    elem: Box::new(ty),
+                });
+            }
+            ty
+        };
+        assert!(RsType::try_from(&nested(63)).is_ok());
+        let mut err = RsType::try_from(&nested(100)).unwrap_err();
+        while let Some(source) = err.source {
+            err = *source;
+        }
+        assert_eq!(
+            err.msg.as_deref(),
+            Some("type is nested more than 64 levels deep")
+        );
+        set_type_depth_limit(128);
+        assert!(RsType::try_from(&nested(100)).is_ok());
+        set_type_depth_limit(DEFAULT_TYPE_DEPTH_LIMIT);
+    }
+
+    #[test]
+    fn field_docs() {
+        let item: ItemStruct = syn::parse_quote! {
+            struct User {
+                /// The user id.
+                ///
+                /// Never zero.
+                id: u64,
+                #[doc = "The age."]
+                age: u8,
+                name_len: usize,
+            }
+        };
+        let user = RsStruct::try_from(&item).unwrap();
+        let docs = user
+            .fields
+            .iter()
+            .map(|f| f.doc.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            docs,
+            [Some("The user id.\n\nNever zero."), Some("The age."), None]
+        );
+    }
+
+    #[test]
+    fn struct_field_shapes() {
+        let item: ItemStruct = syn::parse_quote! {
+            struct Point {
+                x: i32,
+                y: i32,
+            }
+        };
+        assert_eq!(RsStruct::try_from(&item).unwrap(), point());
+        let item: ItemStruct = syn::parse_quote!(
+            struct Pair(i32, bool);
+        );
+        let pair = RsStruct::try_from(&item).unwrap();
+        assert_eq!(
+            pair.fields,
+            [
+                RsField::new("field_0".to_string(), i32_ty()),
+                RsField::new("field_1".to_string(), RsPrimitive::Bool.into()),
+            ]
+        );
+        let item: ItemStruct = syn::parse_quote!(
+            struct Marker;
+        );
+        assert!(RsStruct::try_from(&item).unwrap().fields.is_empty());
+    }
+
+    #[test]
+    fn enum_variant_field_shapes() {
+        let item: ItemEnum = syn::parse_quote! {
+            enum Shape {
+                Circle(f32),
+                Rect { w: f32, h: f32 },
+                Empty,
+            }
+        };
+        let shape = RsEnum::try_from(&item).unwrap();
+        let names = shape
+            .variants
+            .iter()
+            .map(|v| v.fields.iter().map(|f| f.name.as_str()).collect())
+            .collect::<Vec<Vec<_>>>();
+        assert_eq!(names, [vec!["field_0"], vec!["w", "h"], vec![]]);
+        let item: ItemStruct = syn::parse_quote!(
+            struct Radius(f32);
+        );
+        let field = item.fields.iter().next().unwrap();
+        assert_eq!(field.description(), "field f32");
+    }
+
+    #[test]
+    fn unsupported_fn_args_are_errors() {
+        let file: syn::File = syn::parse_quote! {
+            fn pair((a, b): (i32, i32)) {}
+            fn bytes(_: [u8]) {}
+        };
+        for item in &file.items {
+            let syn::Item::Fn(f) = item else {
+                unreachable!()
+            };
+            assert!(RsFn::try_from(f).is_err());
+        }
     }
 }